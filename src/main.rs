@@ -1,22 +1,146 @@
-use aws_config::Region;
+use aws_config::{
+    default_provider::credentials::default_provider,
+    ecs::EcsCredentialsProvider,
+    imds::credentials::ImdsCredentialsProvider,
+    web_identity_token::WebIdentityTokenCredentialsProvider,
+    Region,
+};
 use aws_sdk_s3::{
-    config::{self, Credentials},
+    config::{self, Credentials, SharedCredentialsProvider},
+    presigning::PresigningConfig,
     primitives::ByteStream,
+    types::{BucketLocationConstraint, CompletedMultipartUpload, CompletedPart, CreateBucketConfiguration},
     Client,
 };
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
+use sha2::{Digest, Sha256};
 use axum::{
-    extract::Multipart, http::StatusCode, routing::{get, post}, Extension, Json, Router
+    body::Body,
+    extract::{Multipart, Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Extension, Json, Router
 };
-use serde::Serialize;
-use tokio_util::bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use tokio_util::bytes::BytesMut;
+use tokio_util::io::ReaderStream;
 use std::sync::Arc;
+use std::time::Duration;
 use tower_http::cors::{CorsLayer, Any};
 use dotenv::dotenv;
 
-#[derive(Serialize)]
+/// S3's minimum part size for all but the final part of a multipart upload.
+const MULTIPART_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Default lifetime for a presigned URL when the caller doesn't specify `expires_in`.
+const DEFAULT_PRESIGN_EXPIRES_IN_SECS: u64 = 3600;
+
+#[derive(Serialize, Default)]
 struct ResponseMessage {
     status: u16,
     message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checksum_algorithm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+}
+
+/// The checksum algorithm used to verify upload integrity, selected via `CHECKSUM_ALGORITHM`.
+enum ChecksumAlgorithmChoice {
+    Sha256,
+    Crc32C,
+}
+
+impl ChecksumAlgorithmChoice {
+    fn from_env() -> Option<Self> {
+        match std::env::var("CHECKSUM_ALGORITHM").ok()?.to_lowercase().as_str() {
+            "sha256" => Some(Self::Sha256),
+            "crc32c" => Some(Self::Crc32C),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Crc32C => "crc32c",
+        }
+    }
+
+    /// Computes the base64-encoded digest S3 expects for `checksum_sha256`/`checksum_crc32_c`.
+    fn digest(&self, data: &[u8]) -> String {
+        match self {
+            Self::Sha256 => {
+                let mut hasher = Sha256::new();
+                hasher.update(data);
+                BASE64_STANDARD.encode(hasher.finalize())
+            }
+            Self::Crc32C => BASE64_STANDARD.encode(crc32c::crc32c(data).to_be_bytes()),
+        }
+    }
+}
+
+/// The checksum computed for a completed upload, surfaced back to the client.
+struct UploadChecksum {
+    algorithm: String,
+    // `None` for multipart uploads: each part is checksummed individually, so there's no
+    // single whole-object digest to report.
+    digest: Option<String>,
+}
+
+/// Everything that can go wrong while streaming a multipart field to S3: either the field
+/// itself fails to read (client disconnect, malformed multipart body) or S3 rejects a call.
+/// Kept distinct from `aws_sdk_s3::Error` so a stream read failure still reaches the
+/// `abort_multipart_upload` cleanup instead of panicking past it.
+#[derive(Debug)]
+enum UploadError {
+    Stream(axum::extract::multipart::MultipartError),
+    S3(aws_sdk_s3::Error),
+}
+
+impl From<axum::extract::multipart::MultipartError> for UploadError {
+    fn from(err: axum::extract::multipart::MultipartError) -> Self {
+        UploadError::Stream(err)
+    }
+}
+
+impl From<aws_sdk_s3::Error> for UploadError {
+    fn from(err: aws_sdk_s3::Error) -> Self {
+        UploadError::S3(err)
+    }
+}
+
+#[derive(Serialize)]
+struct PresignResponseMessage {
+    status: u16,
+    message: String,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct PresignQuery {
+    key: String,
+    operation: String,
+    expires_in: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ListObjectsQuery {
+    prefix: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ObjectSummary {
+    key: String,
+    size: i64,
+    last_modified: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ListObjectsResponse {
+    status: u16,
+    objects: Vec<ObjectSummary>,
 }
 
 #[tokio::main]
@@ -28,13 +152,20 @@ async fn main() {
     dotenv().ok();
 
     // Initialize the AWS client
-    let s3_client = get_aws_client();
+    let s3_client = get_aws_client().await;
+
+    // Make sure the configured bucket exists before we start serving traffic
+    ensure_bucket_exists(&s3_client).await;
 
     // Wrap the client in an Arc to share it safely
     let shared_s3_client = Arc::new(s3_client);
 
     let app = Router::new()
         .route("/upload", post(upload_handler))
+        .route("/presign", get(presign_handler))
+        .route("/download/:key", get(download_handler))
+        .route("/objects", get(list_objects_handler))
+        .route("/objects/:key", delete(delete_object_handler))
         .route("/ping", get(get_ping))
         .fallback(handler_404)
         .layer(CorsLayer::new().allow_origin(Any))
@@ -47,7 +178,8 @@ async fn main() {
 async fn handler_404() -> (StatusCode, Json<ResponseMessage>) {
     (StatusCode::NOT_FOUND, Json(ResponseMessage{
         status: StatusCode::NOT_FOUND.as_u16(),
-        message: "404 not found".to_string()
+        message: "404 not found".to_string(),
+        ..Default::default()
     }))
 }
 
@@ -55,24 +187,185 @@ async fn get_ping() -> &'static str {
     "pong!"
 }
 
-fn get_aws_client() -> Client {
+async fn list_objects_handler(
+    Extension(s3_client): Extension<Arc<Client>>,
+    Query(params): Query<ListObjectsQuery>,
+) -> (StatusCode, Json<ListObjectsResponse>) {
+    let bucket_name = std::env::var("BUCKET_NAME")
+        .expect("cannot find BUCKET_NAME env");
+
+    let mut objects = Vec::new();
+    let mut continuation_token = None;
+
+    loop {
+        let mut request = s3_client.list_objects_v2().bucket(&bucket_name);
+        if let Some(prefix) = &params.prefix {
+            request = request.prefix(prefix);
+        }
+        if let Some(token) = &continuation_token {
+            request = request.continuation_token(token);
+        }
+
+        let result = match request.send().await {
+            Ok(result) => result,
+            Err(_) => {
+                return (StatusCode::INTERNAL_SERVER_ERROR, Json(ListObjectsResponse{
+                    status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+                    objects: Vec::new(),
+                }));
+            }
+        };
+
+        for object in result.contents() {
+            objects.push(ObjectSummary{
+                key: object.key().unwrap_or_default().to_string(),
+                size: object.size().unwrap_or_default(),
+                last_modified: object.last_modified().map(|timestamp| timestamp.to_string()),
+            });
+        }
+
+        if !result.is_truncated().unwrap_or(false) {
+            break;
+        }
+        // Truncated with no token to resume from would otherwise spin on page 1 forever.
+        continuation_token = match result.next_continuation_token() {
+            Some(token) => Some(token.to_string()),
+            None => break,
+        };
+    }
+
+    (StatusCode::OK, Json(ListObjectsResponse{
+        status: StatusCode::OK.as_u16(),
+        objects,
+    }))
+}
+
+async fn delete_object_handler(
+    Extension(s3_client): Extension<Arc<Client>>,
+    Path(key): Path<String>,
+) -> (StatusCode, Json<ResponseMessage>) {
+    let bucket_name = std::env::var("BUCKET_NAME")
+        .expect("cannot find BUCKET_NAME env");
+
+    match s3_client.delete_object().bucket(&bucket_name).key(&key).send().await {
+        Ok(_) => (StatusCode::OK, Json(ResponseMessage{
+            status: StatusCode::OK.as_u16(),
+            message: "Object deleted successfully".to_string(),
+            ..Default::default()
+        })),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ResponseMessage{
+            status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            message: "Failed to delete object".to_string(),
+            ..Default::default()
+        })),
+    }
+}
+
+async fn download_handler(
+    Extension(s3_client): Extension<Arc<Client>>,
+    Path(key): Path<String>,
+) -> Response {
+    let bucket_name = std::env::var("BUCKET_NAME")
+        .expect("cannot find BUCKET_NAME env");
+
+    let object = match s3_client.get_object().bucket(&bucket_name).key(&key).send().await {
+        Ok(object) => object,
+        Err(_) => {
+            return (StatusCode::NOT_FOUND, Json(ResponseMessage{
+                status: StatusCode::NOT_FOUND.as_u16(),
+                message: "File not found".to_string(),
+                ..Default::default()
+            })).into_response();
+        }
+    };
+
+    let content_type = object.content_type().unwrap_or("application/octet-stream").to_string();
+    let content_length = object.content_length();
+
+    // Stream the object body straight through to the client without buffering it whole.
+    let stream = ReaderStream::new(object.body.into_async_read());
+    let body = Body::from_stream(stream);
+
+    let mut response_builder = Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type);
+    if let Some(length) = content_length {
+        response_builder = response_builder.header(header::CONTENT_LENGTH, length.to_string());
+    }
+
+    response_builder.body(body).unwrap()
+}
+
+async fn presign_handler(
+    Extension(s3_client): Extension<Arc<Client>>,
+    Query(params): Query<PresignQuery>,
+) -> (StatusCode, Json<PresignResponseMessage>) {
+    let bucket_name = std::env::var("BUCKET_NAME")
+        .expect("cannot find BUCKET_NAME env");
+
+    let expires_in = Duration::from_secs(params.expires_in.unwrap_or(DEFAULT_PRESIGN_EXPIRES_IN_SECS));
+    let presigning_config = match PresigningConfig::expires_in(expires_in) {
+        Ok(config) => config,
+        Err(_) => {
+            return (StatusCode::BAD_REQUEST, Json(PresignResponseMessage{
+                status: StatusCode::BAD_REQUEST.as_u16(),
+                message: "Invalid expires_in".to_string(),
+                url: String::new(),
+            }))
+        }
+    };
+
+    let presigned_request = match params.operation.as_str() {
+        "get" => s3_client
+            .get_object()
+            .bucket(&bucket_name)
+            .key(&params.key)
+            .presigned(presigning_config)
+            .await,
+        "put" => s3_client
+            .put_object()
+            .bucket(&bucket_name)
+            .key(&params.key)
+            .presigned(presigning_config)
+            .await,
+        _ => {
+            return (StatusCode::BAD_REQUEST, Json(PresignResponseMessage{
+                status: StatusCode::BAD_REQUEST.as_u16(),
+                message: "operation must be 'get' or 'put'".to_string(),
+                url: String::new(),
+            }))
+        }
+    };
+
+    match presigned_request {
+        Ok(request) => (StatusCode::OK, Json(PresignResponseMessage{
+            status: StatusCode::OK.as_u16(),
+            message: "Presigned URL generated successfully".to_string(),
+            url: request.uri().to_string(),
+        })),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, Json(PresignResponseMessage{
+            status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            message: "Failed to generate presigned URL".to_string(),
+            url: String::new(),
+        })),
+    }
+}
+
+async fn get_aws_client() -> Client {
     let region = std::env::var("REGION")
         .expect("cannot find REGION env");
     let endpoint = std::env::var("ENDPOINT")
         .expect("cannot find ENDPOINT env");
-    let aws3_cred_key_id = std::env::var("AWS3_CRED_KEY_ID")
-        .expect("cannot find AWS3_CRED_KEY_ID env");
-    let aws3_cred_key_secret = std::env::var("AWS3_CRED_KEY_SECRET")
-        .expect("cannot find AWS3_CRED_KEY_SECRET env");
 
-    // build the aws cred
-    let cred = Credentials::new(aws3_cred_key_id, aws3_cred_key_secret, None, None, "local");
+    // build the aws cred provider
+    let credential_source = std::env::var("CREDENTIAL_SOURCE").unwrap_or_else(|_| "env".to_string());
+    let credentials_provider = get_credentials_provider(&credential_source).await;
 
     // build aws config
     let region = Region::new(region.to_string());
     let conf_builder = config::Builder::new()
         .region(region)
-        .credentials_provider(cred)
+        .credentials_provider(credentials_provider)
         .force_path_style(true)
         .endpoint_url(endpoint);
     let conf = conf_builder.build();
@@ -82,6 +375,65 @@ fn get_aws_client() -> Client {
     client
 }
 
+/// Selects a credentials provider based on `CREDENTIAL_SOURCE`, mirroring the standard AWS
+/// credential chain so the same binary runs locally against MinIO and in a real cluster.
+async fn get_credentials_provider(credential_source: &str) -> SharedCredentialsProvider {
+    match credential_source {
+        // ECS/task container credentials (the relative-URI container endpoint).
+        "ecs" => SharedCredentialsProvider::new(EcsCredentialsProvider::builder().build()),
+        // EC2 instance metadata (IMDS).
+        "imds" => SharedCredentialsProvider::new(ImdsCredentialsProvider::builder().build()),
+        // Web-identity-token/OIDC, driven by AWS_WEB_IDENTITY_TOKEN_FILE + role ARN env vars.
+        "web_identity" => SharedCredentialsProvider::new(WebIdentityTokenCredentialsProvider::builder().build()),
+        // The standard AWS credential chain (env -> profile -> web identity -> ECS -> IMDS).
+        "chain" => SharedCredentialsProvider::new(default_provider().await),
+        // Static credentials from env vars, for local development against MinIO.
+        _ => {
+            let aws3_cred_key_id = std::env::var("AWS3_CRED_KEY_ID")
+                .expect("cannot find AWS3_CRED_KEY_ID env");
+            let aws3_cred_key_secret = std::env::var("AWS3_CRED_KEY_SECRET")
+                .expect("cannot find AWS3_CRED_KEY_SECRET env");
+            SharedCredentialsProvider::new(Credentials::new(aws3_cred_key_id, aws3_cred_key_secret, None, None, "local"))
+        }
+    }
+}
+
+/// Makes local/first-run deployments work out of the box: if `BUCKET_NAME` doesn't exist and
+/// `AUTO_CREATE_BUCKET=true` is set, creates it before the server starts serving traffic.
+async fn ensure_bucket_exists(s3_client: &Client) {
+    let bucket_name = std::env::var("BUCKET_NAME")
+        .expect("cannot find BUCKET_NAME env");
+
+    if s3_client.head_bucket().bucket(&bucket_name).send().await.is_ok() {
+        return;
+    }
+
+    let auto_create_bucket = std::env::var("AUTO_CREATE_BUCKET")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    if !auto_create_bucket {
+        return;
+    }
+
+    let region = std::env::var("REGION")
+        .expect("cannot find REGION env");
+
+    let mut create_bucket_request = s3_client.create_bucket().bucket(&bucket_name);
+    // S3 rejects a location constraint of "us-east-1" (it's the implicit default region), so
+    // only attach one for every other region.
+    if region != "us-east-1" {
+        let bucket_configuration = CreateBucketConfiguration::builder()
+            .location_constraint(BucketLocationConstraint::from(region.as_str()))
+            .build();
+        create_bucket_request = create_bucket_request.create_bucket_configuration(bucket_configuration);
+    }
+
+    create_bucket_request
+        .send()
+        .await
+        .expect("failed to auto-create bucket");
+}
+
 async fn upload_handler(
     Extension(s3_client): Extension<Arc<Client>>,
     mut multipart: Multipart,
@@ -94,23 +446,27 @@ async fn upload_handler(
         let content_type = field.content_type().unwrap().to_string();
         let file_name = field.file_name().unwrap().to_string();
 
-        // Perform mutable borrow
-        let data = field.bytes().await.unwrap();
-
-        // Call the function to upload to S3
-        let result = upload_to_s3(&s3_client, file_name, content_type.as_str(), data.clone(), &bucket_name).await;
+        // Call the function to upload to S3, streaming the field straight through
+        let result = upload_to_s3(&s3_client, file_name, content_type.as_str(), field, &bucket_name).await;
 
         match result {
-            Ok(_) => {
+            Ok(checksum) => {
+                let (checksum_algorithm, checksum) = match checksum {
+                    Some(c) => (Some(c.algorithm), c.digest),
+                    None => (None, None),
+                };
                 return (StatusCode::CREATED, Json(ResponseMessage{
                     status: StatusCode::CREATED.as_u16(),
-                    message: "File uploaded successfully".to_string()
+                    message: "File uploaded successfully".to_string(),
+                    checksum_algorithm,
+                    checksum,
                 }))
             },
             Err(_) => {
                 return (StatusCode::INTERNAL_SERVER_ERROR, Json(ResponseMessage{
                     status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
-                    message: "Failed to upload file".to_string()
+                    message: "Failed to upload file".to_string(),
+                    ..Default::default()
                 }))
             },
         }
@@ -118,7 +474,8 @@ async fn upload_handler(
 
     (StatusCode::BAD_REQUEST, Json(ResponseMessage{
         status: StatusCode::BAD_REQUEST.as_u16(),
-        message: "No file found".to_string()
+        message: "No file found".to_string(),
+        ..Default::default()
     }))
 }
 
@@ -126,16 +483,160 @@ async fn upload_to_s3(
     s3_client: &Client,
     file_name: String,
     content_type: &str,
-    data: Bytes,
+    mut field: axum::extract::multipart::Field<'_>,
     bucket_name: &String
-) -> Result<(), aws_sdk_s3::Error> {
+) -> Result<Option<UploadChecksum>, UploadError> {
     println!("{} {}", file_name, content_type);
-    let req = s3_client
-        .put_object()
+
+    let checksum_algorithm = ChecksumAlgorithmChoice::from_env();
+
+    // Read just enough of the field to know whether it clears the multipart part-size
+    // threshold, without ever holding more than one part's worth of bytes in memory.
+    let mut buffer = BytesMut::new();
+    while buffer.len() < MULTIPART_PART_SIZE {
+        match field.chunk().await? {
+            Some(chunk) => buffer.extend_from_slice(&chunk),
+            None => break,
+        }
+    }
+
+    // Small file: a single put_object is simpler and cheaper than a multipart upload.
+    if buffer.len() < MULTIPART_PART_SIZE {
+        let data = buffer.freeze();
+        let mut req = s3_client
+            .put_object()
+            .bucket(bucket_name)
+            .body(ByteStream::from(data.clone()))
+            .content_type(content_type)
+            .key(file_name);
+
+        let mut upload_checksum = None;
+        if let Some(algorithm) = &checksum_algorithm {
+            let digest = algorithm.digest(&data);
+            req = match algorithm {
+                ChecksumAlgorithmChoice::Sha256 => req.checksum_sha256(&digest),
+                ChecksumAlgorithmChoice::Crc32C => req.checksum_crc32_c(&digest),
+            };
+            upload_checksum = Some(UploadChecksum{ algorithm: algorithm.as_str().to_string(), digest: Some(digest) });
+        }
+
+        req.send().await.map_err(aws_sdk_s3::Error::from)?;
+        return Ok(upload_checksum);
+    }
+
+    let mut create_request = s3_client
+        .create_multipart_upload()
         .bucket(bucket_name)
-        .body(ByteStream::from(data))
-        .content_type(content_type)
-        .key(file_name);
-    req.send().await?;
-    Ok(())
+        .key(&file_name)
+        .content_type(content_type);
+    if let Some(algorithm) = &checksum_algorithm {
+        create_request = create_request.checksum_algorithm(match algorithm {
+            ChecksumAlgorithmChoice::Sha256 => aws_sdk_s3::types::ChecksumAlgorithm::Sha256,
+            ChecksumAlgorithmChoice::Crc32C => aws_sdk_s3::types::ChecksumAlgorithm::Crc32C,
+        });
+    }
+    let create_result = create_request.send().await.map_err(aws_sdk_s3::Error::from)?;
+    let upload_id = create_result.upload_id().unwrap().to_string();
+
+    let upload_result = upload_parts_streaming(s3_client, &file_name, bucket_name, &upload_id, &mut field, buffer, checksum_algorithm.as_ref()).await;
+
+    match upload_result {
+        Ok(parts) => {
+            let completed_upload = CompletedMultipartUpload::builder()
+                .set_parts(Some(parts))
+                .build();
+            s3_client
+                .complete_multipart_upload()
+                .bucket(bucket_name)
+                .key(&file_name)
+                .upload_id(&upload_id)
+                .multipart_upload(completed_upload)
+                .send()
+                .await
+                .map_err(aws_sdk_s3::Error::from)?;
+            // Each part's checksum was already validated by S3 as it arrived; there's no
+            // single whole-object digest to report for a multipart upload.
+            Ok(checksum_algorithm.map(|algorithm| UploadChecksum{ algorithm: algorithm.as_str().to_string(), digest: None }))
+        }
+        Err(err) => {
+            // Don't leave orphaned, billable parts behind when a part upload fails, whether
+            // the failure was S3 rejecting a part or the client disconnecting mid-stream.
+            let _ = s3_client
+                .abort_multipart_upload()
+                .bucket(bucket_name)
+                .key(&file_name)
+                .upload_id(&upload_id)
+                .send()
+                .await;
+            Err(err)
+        }
+    }
+}
+
+/// Streams the rest of `field` to S3 as multipart parts of at least `MULTIPART_PART_SIZE`
+/// bytes each (the final part may be smaller), starting from an already-filled `buffer`.
+async fn upload_parts_streaming(
+    s3_client: &Client,
+    file_name: &str,
+    bucket_name: &String,
+    upload_id: &str,
+    field: &mut axum::extract::multipart::Field<'_>,
+    mut buffer: BytesMut,
+    checksum_algorithm: Option<&ChecksumAlgorithmChoice>,
+) -> Result<Vec<CompletedPart>, UploadError> {
+    let mut parts = Vec::new();
+    let mut part_number = 1;
+
+    loop {
+        let part_bytes = buffer.split().freeze();
+
+        let mut upload_part_request = s3_client
+            .upload_part()
+            .bucket(bucket_name)
+            .key(file_name)
+            .upload_id(upload_id)
+            .part_number(part_number);
+        // Captured so it can also be set on the matching `CompletedPart` below: when the
+        // multipart upload was created with a checksum algorithm, S3 requires every
+        // `CompletedPart` passed to `complete_multipart_upload` to carry that part's checksum.
+        let mut part_digest = None;
+        if let Some(algorithm) = checksum_algorithm {
+            let digest = algorithm.digest(&part_bytes);
+            upload_part_request = match algorithm {
+                ChecksumAlgorithmChoice::Sha256 => upload_part_request.checksum_sha256(&digest),
+                ChecksumAlgorithmChoice::Crc32C => upload_part_request.checksum_crc32_c(&digest),
+            };
+            part_digest = Some(digest);
+        }
+        let upload_part_result = upload_part_request
+            .body(ByteStream::from(part_bytes))
+            .send()
+            .await
+            .map_err(aws_sdk_s3::Error::from)?;
+
+        let mut completed_part = CompletedPart::builder()
+            .e_tag(upload_part_result.e_tag().unwrap_or_default())
+            .part_number(part_number);
+        if let (Some(algorithm), Some(digest)) = (checksum_algorithm, part_digest) {
+            completed_part = match algorithm {
+                ChecksumAlgorithmChoice::Sha256 => completed_part.checksum_sha256(digest),
+                ChecksumAlgorithmChoice::Crc32C => completed_part.checksum_crc32_c(digest),
+            };
+        }
+        parts.push(completed_part.build());
+        part_number += 1;
+
+        while buffer.len() < MULTIPART_PART_SIZE {
+            match field.chunk().await? {
+                Some(chunk) => buffer.extend_from_slice(&chunk),
+                None => break,
+            }
+        }
+
+        if buffer.is_empty() {
+            break;
+        }
+    }
+
+    Ok(parts)
 }
\ No newline at end of file